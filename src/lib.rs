@@ -1,3 +1,5 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A distributed unique ID generator inspired by Twitter's Snowflake.
@@ -8,49 +10,88 @@ pub struct SnowflakeGenerator {
     /// Time cut-off
     last_time_millis: u128,
 
-    /// Work Machine ID (0-31)
+    /// Work Machine ID
     worker_id: u8,
 
-    /// Data Center ID (0-31)
+    /// Data Center ID
     data_center_id: u8,
 
-    /// Sequences in milliseconds (0 - 4095)
-    sequence: u16,
+    /// Sequence within the current millisecond
+    sequence: u32,
 
     /// The time wanted to cut-off
     timestamp_offset: u128,
+
+    /// The mask of the generated sequence for the configured `sequence_bits`
+    sequence_mask: i64,
+
+    /// Left shift applied to `worker_id` when packing an id
+    worker_id_shift: u8,
+
+    /// Left shift applied to `data_center_id` when packing an id
+    data_center_id_shift: u8,
+
+    /// Left shift applied to the timestamp when packing an id
+    timestamp_left_shift: u8,
+
+    /// What to do when the wall clock is observed to have moved backwards
+    clock_backward_policy: ClockBackwardPolicy,
 }
 
-impl SnowflakeGenerator {
-    /// Number of digits occupied by machine id
-    const WORKER_ID_BITS: u8 = 5;
+/// Outcome of a single non-blocking [`SnowflakeGenerator::try_next_id_step`] attempt.
+enum NextIdStep {
+    /// An id was minted.
+    Ready(u128),
 
-    /// Number of digits occupied by the data center identifier id
-    const DATA_CENTER_BITS: u8 = 5;
+    /// The clock moved backwards within the tolerated range; retry once it catches up.
+    WaitForClockToCatchUp,
 
-    /// Number of digits occupied by sequence
-    const SEQUENCE_BITS: u8 = 12;
+    /// The sequence space for the current millisecond is exhausted; retry once the clock
+    /// advances to the next one.
+    WaitForNextMillisecond,
+}
 
-    /// Supported maximum machine id, the result is 31.
-    ///
-    /// this shift algorithm can quickly calculate the maximum decimal number represented by
-    /// serveral bits of binary number
-    const MAX_WORK_ID: i8 = -1 ^ (-1 << SnowflakeGenerator::WORKER_ID_BITS);
+impl SnowflakeGenerator {
+    /// Default number of digits occupied by machine id
+    const DEFAULT_WORKER_ID_BITS: u8 = 5;
 
-    /// Supported maximum data identifier id, the result is 31.
-    const MAX_DATA_CENTER_ID: i8 = -1 ^ (-1 << SnowflakeGenerator::DATA_CENTER_BITS);
+    /// Default number of digits occupied by the data center identifier id
+    const DEFAULT_DATA_CENTER_ID_BITS: u8 = 5;
 
-    /// The mask of the generated sequence is 4095 (0b111111111111111111111 = 0xfff = 4095)
-    const SEQUENCE_MASK: i16 = -1 ^ (-1 << SnowflakeGenerator::SEQUENCE_BITS);
+    /// Default number of digits occupied by sequence
+    const DEFAULT_SEQUENCE_BITS: u8 = 12;
 
-    /// Time truncate moves 22 bits to the left (5 + 5 + 12)
-    const TIMESTAMP_LEFT_SHIFT: u8 = SnowflakeGenerator::SEQUENCE_BITS
-        + SnowflakeGenerator::WORKER_ID_BITS
-        + SnowflakeGenerator::DATA_CENTER_BITS;
+    /// Default number of digits occupied by the timestamp, leaving the
+    /// worker/data-center/sequence defaults above room to sum to 63 bits.
+    const DEFAULT_TIMESTAMP_BITS: u8 = 41;
 
-    /// Create SnowflakeGenerator
+    /// Start building a [`SnowflakeGenerator`] with a custom bit-field layout.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::SnowflakeGenerator;
+    ///
+    /// let mut generator = SnowflakeGenerator::builder()
+    ///     .timestamp_bits(44)
+    ///     .sequence_bits(17)
+    ///     .worker_id_bits(1)
+    ///     .data_center_id_bits(1)
+    ///     .worker_id(1)
+    ///     .build();
+    /// let id = generator.next_id();
+    /// ```
+    pub fn builder() -> SnowflakeGeneratorBuilder {
+        SnowflakeGeneratorBuilder::default()
+    }
+
+    /// Create SnowflakeGenerator with the default bit-field layout (5 worker-id bits,
+    /// 5 data-center-id bits, 12 sequence bits).
     /// Please make sure that worker_id and data_center_id is between 0 - 31.
     ///
+    /// # Panics
+    /// Panics if `worker_id` or `data_center_id` is out of range. See [`Self::try_new`]
+    /// for a non-panicking version.
+    ///
     /// # Example
     /// ```
     /// use snowflake::SnowflakeGenerator;
@@ -58,32 +99,40 @@ impl SnowflakeGenerator {
     /// let mut generator = SnowflakeGenerator::new(0, 0, 0);
     /// ```
     pub fn new(worker_id: u8, data_center_id: u8, timestamp_offset: u128) -> Self {
-        if worker_id as i8 > SnowflakeGenerator::MAX_WORK_ID {
-            panic!(
-                "worker id must be between 0 - {}",
-                SnowflakeGenerator::MAX_WORK_ID
-            );
-        }
-
-        if data_center_id as i8 > SnowflakeGenerator::MAX_DATA_CENTER_ID {
-            panic!(
-                "data center id must be between 0 - {}",
-                SnowflakeGenerator::MAX_DATA_CENTER_ID
-            );
+        match SnowflakeGenerator::try_new(worker_id, data_center_id, timestamp_offset) {
+            Ok(generator) => generator,
+            Err(err) => panic!("{}", err),
         }
+    }
 
-        Self {
-            worker_id,
-            data_center_id,
-            sequence: 0,
-            timestamp_offset,
-            last_time_millis: SnowflakeGenerator::get_current_timestamp(timestamp_offset),
-        }
+    /// Fallible version of [`Self::new`] that reports an out-of-range `worker_id`/
+    /// `data_center_id` as a [`SnowflakeError`] instead of panicking.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::SnowflakeGenerator;
+    ///
+    /// let generator = SnowflakeGenerator::try_new(0, 0, 0);
+    /// assert!(generator.is_ok());
+    /// ```
+    pub fn try_new(
+        worker_id: u8,
+        data_center_id: u8,
+        timestamp_offset: u128,
+    ) -> Result<Self, SnowflakeError> {
+        SnowflakeGenerator::builder()
+            .worker_id(worker_id)
+            .data_center_id(data_center_id)
+            .epoch(timestamp_offset)
+            .try_build()
     }
 
     /// Get the next id.
-    /// This function will panic if the system time has changed and the time is less than generator
-    /// last_time_millis
+    ///
+    /// # Panics
+    /// Panics if the system clock has moved backwards by more than the generator's
+    /// `clock_backward_policy` tolerates. See [`Self::try_next_id`] for a non-panicking
+    /// version.
     ///
     /// # Example
     /// ```
@@ -93,36 +142,235 @@ impl SnowflakeGenerator {
     /// let id = generator.next_id();
     /// ```
     pub fn next_id(&mut self) -> u128 {
-        let mut now = SnowflakeGenerator::get_current_timestamp(self.timestamp_offset);
+        match self.try_next_id() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Fallible version of [`Self::next_id`] that reports a clock moving backwards beyond
+    /// what the generator's `clock_backward_policy` tolerates as a [`SnowflakeError`]
+    /// instead of panicking.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::SnowflakeGenerator;
+    ///
+    /// let mut generator = SnowflakeGenerator::new(0, 0, 0);
+    /// let id = generator.try_next_id().expect("clock moved backwards");
+    /// ```
+    pub fn try_next_id(&mut self) -> Result<u128, SnowflakeError> {
+        loop {
+            match self.try_next_id_step()? {
+                NextIdStep::Ready(id) => return Ok(id),
+                NextIdStep::WaitForClockToCatchUp => {
+                    SnowflakeGenerator::wait_for_clock_to_catch_up(
+                        self.last_time_millis,
+                        self.timestamp_offset,
+                    );
+                }
+                NextIdStep::WaitForNextMillisecond => {
+                    SnowflakeGenerator::til_next_milliseconds(
+                        self.last_time_millis,
+                        self.timestamp_offset,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Attempt to mint the next id without blocking.
+    ///
+    /// This is the single timestamp-read/sequence-increment critical section of id
+    /// generation, split out so [`ConcurrentSnowflakeGenerator`] can release its mutex
+    /// while waiting for a tolerated clock rollback to pass. The wait for the next
+    /// millisecond after sequence exhaustion still runs with the mutex held.
+    fn try_next_id_step(&mut self) -> Result<NextIdStep, SnowflakeError> {
+        let now = SnowflakeGenerator::get_current_timestamp(self.timestamp_offset);
 
         if now < self.last_time_millis {
-            panic!(
-                "Clock moved backwards, refusing to generate id for {} milliseconds.",
-                self.last_time_millis - now
-            );
+            let by_millis = self.last_time_millis - now;
+
+            return match self.clock_backward_policy {
+                ClockBackwardPolicy::WaitUpTo(tolerance) if by_millis <= tolerance.as_millis() => {
+                    Ok(NextIdStep::WaitForClockToCatchUp)
+                }
+                ClockBackwardPolicy::Panic => {
+                    panic!("{}", SnowflakeError::ClockMovedBackwards { by_millis })
+                }
+                ClockBackwardPolicy::WaitUpTo(_) | ClockBackwardPolicy::Error => {
+                    Err(SnowflakeError::ClockMovedBackwards { by_millis })
+                }
+            };
         }
 
         if self.last_time_millis == now {
-            self.sequence =
-                (((self.sequence + 1) as i16) % SnowflakeGenerator::SEQUENCE_MASK) as u16;
+            self.sequence = ((self.sequence as i64 + 1) & self.sequence_mask) as u32;
             if self.sequence == 0 {
-                now = SnowflakeGenerator::til_next_milliseconds(
-                    self.last_time_millis,
-                    self.timestamp_offset,
-                );
+                return Ok(NextIdStep::WaitForNextMillisecond);
             }
         } else {
             self.sequence = 0;
+            self.last_time_millis = now;
+        }
+
+        Ok(NextIdStep::Ready(self.pack_id()))
+    }
+
+    /// Generate `n` ids in one call.
+    ///
+    /// Unlike calling [`Self::next_id`] in a loop, this only reads the system clock once
+    /// per millisecond spanned by the batch rather than once per id, rolling over to the
+    /// next millisecond only when the sequence space within the current one is exhausted.
+    /// Ids are still strictly monotonically increasing, including across the millisecond
+    /// boundaries a large batch may span.
+    ///
+    /// # Panics
+    /// Panics if the system clock has moved backwards by more than the generator's
+    /// `clock_backward_policy` tolerates. See [`Self::try_next_id_batch`] for a
+    /// non-panicking version.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::SnowflakeGenerator;
+    ///
+    /// let mut generator = SnowflakeGenerator::new(0, 0, 0);
+    /// let ids = generator.next_id_batch(100);
+    /// assert_eq!(ids.len(), 100);
+    /// ```
+    pub fn next_id_batch(&mut self, n: usize) -> Vec<u128> {
+        match self.try_next_id_batch(n) {
+            Ok(ids) => ids,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Fallible version of [`Self::next_id_batch`] that reports a clock moving backwards
+    /// beyond what the generator's `clock_backward_policy` tolerates as a
+    /// [`SnowflakeError`] instead of panicking.
+    pub fn try_next_id_batch(&mut self, n: usize) -> Result<Vec<u128>, SnowflakeError> {
+        let mut ids = Vec::with_capacity(n);
+
+        while ids.len() < n {
+            let mut now = SnowflakeGenerator::get_current_timestamp(self.timestamp_offset);
+
+            if now < self.last_time_millis {
+                now = self.wait_out_clock_backward(now)?;
+            }
+
+            if now > self.last_time_millis {
+                self.last_time_millis = now;
+                self.sequence = 0;
+            } else {
+                // Still the same millisecond a previous id was minted in: advance past it
+                // before emitting the first id of this batch, just like `try_next_id` does.
+                self.sequence = ((self.sequence as i64 + 1) & self.sequence_mask) as u32;
+                if self.sequence == 0 {
+                    self.last_time_millis = SnowflakeGenerator::til_next_milliseconds(
+                        self.last_time_millis,
+                        self.timestamp_offset,
+                    );
+                }
+            }
+
+            loop {
+                ids.push(self.pack_id());
+                if ids.len() == n {
+                    break;
+                }
+
+                self.sequence = ((self.sequence as i64 + 1) & self.sequence_mask) as u32;
+                if self.sequence == 0 {
+                    self.last_time_millis = SnowflakeGenerator::til_next_milliseconds(
+                        self.last_time_millis,
+                        self.timestamp_offset,
+                    );
+                }
+            }
         }
 
-        self.last_time_millis = now;
+        Ok(ids)
+    }
 
-        (self.last_time_millis << SnowflakeGenerator::TIMESTAMP_LEFT_SHIFT) as u128
-            | (self.data_center_id << SnowflakeGenerator::DATA_CENTER_BITS) as u128
-            | (self.worker_id << SnowflakeGenerator::WORKER_ID_BITS) as u128
+    /// Pack the generator's current state into an id.
+    fn pack_id(&self) -> u128 {
+        (self.last_time_millis << self.timestamp_left_shift)
+            | (self.data_center_id as u128) << self.data_center_id_shift
+            | (self.worker_id as u128) << self.worker_id_shift
             | self.sequence as u128
     }
 
+    /// Decode a previously generated id back into its components, using this generator's
+    /// bit-field layout. The same shifts `next_id` used to pack the id are used here to
+    /// unpack it, so this stays consistent with any layout configured through the builder.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::SnowflakeGenerator;
+    ///
+    /// let mut generator = SnowflakeGenerator::new(1, 2, 0);
+    /// let id = generator.next_id();
+    /// let parts = generator.decode(id);
+    ///
+    /// assert_eq!(parts.worker_id, 1);
+    /// assert_eq!(parts.data_center_id, 2);
+    /// ```
+    pub fn decode(&self, id: u128) -> SnowflakeParts {
+        let sequence_mask = (1u128 << self.worker_id_shift) - 1;
+        let worker_id_mask = (1u128 << (self.data_center_id_shift - self.worker_id_shift)) - 1;
+        let data_center_id_mask =
+            (1u128 << (self.timestamp_left_shift - self.data_center_id_shift)) - 1;
+
+        let timestamp_millis = id >> self.timestamp_left_shift;
+        let data_center_id = ((id >> self.data_center_id_shift) & data_center_id_mask) as u8;
+        let worker_id = ((id >> self.worker_id_shift) & worker_id_mask) as u8;
+        let sequence = (id & sequence_mask) as u32;
+
+        let datetime =
+            UNIX_EPOCH + Duration::from_millis((timestamp_millis + self.timestamp_offset) as u64);
+
+        SnowflakeParts {
+            timestamp_millis,
+            datetime,
+            data_center_id,
+            worker_id,
+            sequence,
+        }
+    }
+
+    /// Handle the wall clock having moved backwards, per `clock_backward_policy`. Returns
+    /// the corrected "now" once the clock has caught back up, or a
+    /// [`SnowflakeError::ClockMovedBackwards`] if the backward jump isn't tolerated.
+    fn wait_out_clock_backward(&self, now: u128) -> Result<u128, SnowflakeError> {
+        let by_millis = self.last_time_millis - now;
+
+        match self.clock_backward_policy {
+            ClockBackwardPolicy::WaitUpTo(tolerance) if by_millis <= tolerance.as_millis() => {
+                Ok(SnowflakeGenerator::wait_for_clock_to_catch_up(
+                    self.last_time_millis,
+                    self.timestamp_offset,
+                ))
+            }
+            ClockBackwardPolicy::Panic => {
+                panic!("{}", SnowflakeError::ClockMovedBackwards { by_millis })
+            }
+            ClockBackwardPolicy::WaitUpTo(_) | ClockBackwardPolicy::Error => {
+                Err(SnowflakeError::ClockMovedBackwards { by_millis })
+            }
+        }
+    }
+
+    /// Block until the wall clock has caught back up to `last_time_millis`.
+    fn wait_for_clock_to_catch_up(last_time_millis: u128, offset: u128) -> u128 {
+        loop {
+            let now = SnowflakeGenerator::get_current_timestamp(offset);
+            if now >= last_time_millis {
+                return now;
+            }
+            std::thread::sleep(Duration::from_micros(100));
+        }
+    }
+
     /// Block to the next milliseconds until a new timestamp is obtained.
     fn til_next_milliseconds(last_time_millis: u128, offset: u128) -> u128 {
         loop {
@@ -144,13 +392,584 @@ impl SnowflakeGenerator {
     }
 }
 
+/// Builds a [`SnowflakeGenerator`] with a configurable bit-field layout.
+///
+/// The default layout matches the original fixed one: 41 timestamp bits, 5
+/// data-center-id bits, 5 worker-id bits and 12 sequence bits (63 bits total).
+/// Deployments that need more than 1024 nodes or a higher per-node throughput
+/// can re-partition the 64 bits, e.g. 44 timestamp bits / 17 sequence bits / 2
+/// combined worker+data-center bits for very high single-node throughput.
+pub struct SnowflakeGeneratorBuilder {
+    timestamp_bits: u8,
+    worker_id_bits: u8,
+    data_center_id_bits: u8,
+    sequence_bits: u8,
+    epoch: u128,
+    worker_id: u8,
+    data_center_id: u8,
+    clock_backward_policy: ClockBackwardPolicy,
+}
+
+impl Default for SnowflakeGeneratorBuilder {
+    fn default() -> Self {
+        Self {
+            timestamp_bits: SnowflakeGenerator::DEFAULT_TIMESTAMP_BITS,
+            worker_id_bits: SnowflakeGenerator::DEFAULT_WORKER_ID_BITS,
+            data_center_id_bits: SnowflakeGenerator::DEFAULT_DATA_CENTER_ID_BITS,
+            sequence_bits: SnowflakeGenerator::DEFAULT_SEQUENCE_BITS,
+            epoch: 0,
+            worker_id: 0,
+            data_center_id: 0,
+            clock_backward_policy: ClockBackwardPolicy::default(),
+        }
+    }
+}
+
+impl SnowflakeGeneratorBuilder {
+    /// Number of bits reserved for the timestamp. Only used to validate that the full
+    /// layout sums to 63 bits or fewer.
+    pub fn timestamp_bits(mut self, bits: u8) -> Self {
+        self.timestamp_bits = bits;
+        self
+    }
+
+    /// Number of bits reserved for the worker id.
+    pub fn worker_id_bits(mut self, bits: u8) -> Self {
+        self.worker_id_bits = bits;
+        self
+    }
+
+    /// Number of bits reserved for the data center id.
+    pub fn data_center_id_bits(mut self, bits: u8) -> Self {
+        self.data_center_id_bits = bits;
+        self
+    }
+
+    /// Number of bits reserved for the per-millisecond sequence.
+    pub fn sequence_bits(mut self, bits: u8) -> Self {
+        self.sequence_bits = bits;
+        self
+    }
+
+    /// The epoch to subtract from the system clock, in milliseconds.
+    pub fn epoch(mut self, epoch: u128) -> Self {
+        self.epoch = epoch;
+        self
+    }
+
+    /// The worker id this generator will stamp onto every id.
+    pub fn worker_id(mut self, worker_id: u8) -> Self {
+        self.worker_id = worker_id;
+        self
+    }
+
+    /// The data center id this generator will stamp onto every id.
+    pub fn data_center_id(mut self, data_center_id: u8) -> Self {
+        self.data_center_id = data_center_id;
+        self
+    }
+
+    /// What to do when the wall clock is observed to have moved backwards. Defaults to
+    /// [`ClockBackwardPolicy::Error`], matching the original behavior.
+    pub fn on_clock_backward(mut self, policy: ClockBackwardPolicy) -> Self {
+        self.clock_backward_policy = policy;
+        self
+    }
+
+    /// Validate the configured layout and build the generator.
+    ///
+    /// # Panics
+    /// Panics if the configured bit widths sum to more than 63 bits, or if `worker_id`/
+    /// `data_center_id` don't fit within the configured `worker_id_bits`/`data_center_id_bits`.
+    /// See [`Self::try_build`] for a version that reports the latter as a
+    /// [`SnowflakeError`] instead of panicking.
+    pub fn build(self) -> SnowflakeGenerator {
+        match self.try_build() {
+            Ok(generator) => generator,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Fallible version of [`Self::build`] that reports an out-of-range `worker_id`/
+    /// `data_center_id` as a [`SnowflakeError`] instead of panicking.
+    ///
+    /// # Panics
+    /// Still panics if the configured bit widths sum to more than 63 bits: that is a
+    /// misconfiguration of the layout itself, not a recoverable runtime condition.
+    pub fn try_build(self) -> Result<SnowflakeGenerator, SnowflakeError> {
+        let total_bits = self.timestamp_bits as u16
+            + self.worker_id_bits as u16
+            + self.data_center_id_bits as u16
+            + self.sequence_bits as u16;
+
+        if total_bits > 63 {
+            panic!(
+                "bit-field layout must sum to 63 bits or fewer, got {} (timestamp: {}, worker_id: {}, data_center_id: {}, sequence: {})",
+                total_bits, self.timestamp_bits, self.worker_id_bits, self.data_center_id_bits, self.sequence_bits
+            );
+        }
+
+        let max_worker_id: i64 = -1 ^ (-1i64 << self.worker_id_bits);
+        let max_data_center_id: i64 = -1 ^ (-1i64 << self.data_center_id_bits);
+        let sequence_mask: i64 = -1 ^ (-1i64 << self.sequence_bits);
+
+        if self.worker_id as i64 > max_worker_id {
+            return Err(SnowflakeError::WorkerIdOutOfRange);
+        }
+
+        if self.data_center_id as i64 > max_data_center_id {
+            return Err(SnowflakeError::DataCenterIdOutOfRange);
+        }
+
+        let worker_id_shift = self.sequence_bits;
+        let data_center_id_shift = self.sequence_bits + self.worker_id_bits;
+        let timestamp_left_shift =
+            self.sequence_bits + self.worker_id_bits + self.data_center_id_bits;
+
+        Ok(SnowflakeGenerator {
+            last_time_millis: SnowflakeGenerator::get_current_timestamp(self.epoch),
+            worker_id: self.worker_id,
+            data_center_id: self.data_center_id,
+            sequence: 0,
+            timestamp_offset: self.epoch,
+            sequence_mask,
+            worker_id_shift,
+            data_center_id_shift,
+            timestamp_left_shift,
+            clock_backward_policy: self.clock_backward_policy,
+        })
+    }
+}
+
+/// Policy applied when the wall clock is observed to have moved backwards relative to the
+/// last timestamp a generator minted an id with (e.g. an NTP correction or a VM pause).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockBackwardPolicy {
+    /// Busy-wait for the clock to catch back up to the last minted timestamp, as long as
+    /// the backward jump is within this tolerance. Beyond it, falls back to `Error`'s
+    /// behavior, as described on [`SnowflakeGenerator::next_id`].
+    WaitUpTo(Duration),
+
+    /// Fail immediately with a [`SnowflakeError::ClockMovedBackwards`], regardless of how
+    /// small the backward jump is. `next_id`/`next_id_batch` still panic on this, since they
+    /// unwrap every `Err`; call `try_next_id`/`try_next_id_batch` to get the `Result` instead.
+    Error,
+
+    /// Panic immediately, regardless of how small the backward jump is or which method was
+    /// called — unlike `Error`, this panics even through `try_next_id`/`try_next_id_batch`.
+    /// For deployments that would rather abort the process than handle a clock rollback.
+    Panic,
+}
+
+impl Default for ClockBackwardPolicy {
+    /// Defaults to [`ClockBackwardPolicy::Error`], matching the original behavior of
+    /// `next_id` (which still panics on the resulting `Err`).
+    fn default() -> Self {
+        ClockBackwardPolicy::Error
+    }
+}
+
+/// Errors that can occur constructing a [`SnowflakeGenerator`] or generating an id, returned
+/// by the `try_*` methods instead of panicking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnowflakeError {
+    /// The given worker id doesn't fit within the configured `worker_id_bits`.
+    WorkerIdOutOfRange,
+
+    /// The given data center id doesn't fit within the configured `data_center_id_bits`.
+    DataCenterIdOutOfRange,
+
+    /// The wall clock moved backwards by more than `clock_backward_policy` tolerates.
+    ClockMovedBackwards {
+        /// How many milliseconds the clock moved backwards by.
+        by_millis: u128,
+    },
+}
+
+impl fmt::Display for SnowflakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnowflakeError::WorkerIdOutOfRange => {
+                write!(
+                    f,
+                    "worker id is out of range for the configured worker_id_bits"
+                )
+            }
+            SnowflakeError::DataCenterIdOutOfRange => write!(
+                f,
+                "data center id is out of range for the configured data_center_id_bits"
+            ),
+            SnowflakeError::ClockMovedBackwards { by_millis } => write!(
+                f,
+                "clock moved backwards, refusing to generate id for {} milliseconds",
+                by_millis
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnowflakeError {}
+
+/// The individual components encoded into a previously generated id.
+///
+/// Returned by [`SnowflakeGenerator::decode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnowflakeParts {
+    /// Milliseconds since the generator's epoch when the id was minted.
+    pub timestamp_millis: u128,
+
+    /// Absolute wall-clock time the id was minted, i.e. `timestamp_millis` plus the
+    /// generator's epoch.
+    pub datetime: SystemTime,
+
+    /// Data center id the id was minted with.
+    pub data_center_id: u8,
+
+    /// Worker id the id was minted with.
+    pub worker_id: u8,
+
+    /// Sequence number within the millisecond the id was minted.
+    pub sequence: u32,
+}
+
+/// A cloneable, thread-safe handle around [`SnowflakeGenerator`].
+///
+/// The generator state (`last_time_millis`/`sequence`) is guarded by a `Mutex` that is
+/// held across the timestamp-read and sequence-increment critical section. A handle
+/// waiting out a tolerated clock rollback releases it meanwhile; a handle waiting for the
+/// next millisecond after sequence exhaustion keeps holding it, so the other handles
+/// block until the clock advances.
+#[derive(Clone)]
+pub struct ConcurrentSnowflakeGenerator {
+    inner: Arc<Mutex<SnowflakeGenerator>>,
+}
+
+impl ConcurrentSnowflakeGenerator {
+    /// Wrap a [`SnowflakeGenerator`] so it can be shared across threads.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::{ConcurrentSnowflakeGenerator, SnowflakeGenerator};
+    ///
+    /// let generator = ConcurrentSnowflakeGenerator::new(SnowflakeGenerator::new(0, 0, 0));
+    /// let id = generator.next_id();
+    /// ```
+    pub fn new(generator: SnowflakeGenerator) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(generator)),
+        }
+    }
+
+    /// Get the next id. Safe to call from many cloned handles at once.
+    ///
+    /// # Panics
+    /// Panics if the system clock has moved backwards by more than the generator's
+    /// `clock_backward_policy` tolerates. See [`Self::try_next_id`] for a non-panicking
+    /// version. This panic happens after the shared mutex is released, so it does not
+    /// poison it. Under [`ClockBackwardPolicy::Panic`], however, the panic happens while
+    /// the mutex is held, through [`Self::try_next_id`] as well, and poisons it for every
+    /// cloned handle.
+    pub fn next_id(&self) -> u128 {
+        match self.try_next_id() {
+            Ok(id) => id,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Fallible version of [`Self::next_id`] that reports a clock moving backwards beyond
+    /// what the generator's `clock_backward_policy` tolerates as a [`SnowflakeError`]
+    /// instead of panicking. Safe to call from many cloned handles at once.
+    ///
+    /// # Example
+    /// ```
+    /// use snowflake::{ConcurrentSnowflakeGenerator, SnowflakeGenerator};
+    ///
+    /// let generator = ConcurrentSnowflakeGenerator::new(SnowflakeGenerator::new(0, 0, 0));
+    /// let id = generator.try_next_id().expect("clock moved backwards");
+    /// ```
+    pub fn try_next_id(&self) -> Result<u128, SnowflakeError> {
+        let mut generator = self
+            .inner
+            .lock()
+            .expect("snowflake generator mutex poisoned");
+
+        loop {
+            match generator.try_next_id_step()? {
+                NextIdStep::Ready(id) => return Ok(id),
+                NextIdStep::WaitForClockToCatchUp => {
+                    // Neither `last_time_millis` nor `sequence` change while waiting for a
+                    // tolerated backward jump, so another handle re-locking meanwhile just
+                    // observes the same state and waits too: safe to release the lock.
+                    let last_time_millis = generator.last_time_millis;
+                    let timestamp_offset = generator.timestamp_offset;
+                    drop(generator);
+
+                    SnowflakeGenerator::wait_for_clock_to_catch_up(
+                        last_time_millis,
+                        timestamp_offset,
+                    );
+
+                    generator = self
+                        .inner
+                        .lock()
+                        .expect("snowflake generator mutex poisoned");
+                }
+                NextIdStep::WaitForNextMillisecond => {
+                    // `sequence` already wrapped to 0 here without `last_time_millis` having
+                    // advanced yet, so the generator is parked in an exhausted millisecond.
+                    // Releasing the lock now would let another handle re-lock, see the same
+                    // exhausted millisecond, and re-mint an id that was already handed out.
+                    // Keep the lock held until the clock actually advances.
+                    SnowflakeGenerator::til_next_milliseconds(
+                        generator.last_time_millis,
+                        generator.timestamp_offset,
+                    );
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::thread;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{
+        ClockBackwardPolicy, ConcurrentSnowflakeGenerator, SnowflakeError, SnowflakeGenerator,
+    };
+
     #[test]
     fn it_works() {
-        let mut generator = super::SnowflakeGenerator::new(0, 0, 0);
+        let mut generator = SnowflakeGenerator::new(0, 0, 0);
         let id = generator.next_id();
 
         assert!(id > 0);
     }
+
+    #[test]
+    fn builder_supports_a_custom_bit_field_layout() {
+        let mut generator = SnowflakeGenerator::builder()
+            .timestamp_bits(44)
+            .sequence_bits(17)
+            .worker_id_bits(1)
+            .data_center_id_bits(1)
+            .worker_id(1)
+            .data_center_id(1)
+            .build();
+
+        let ids: Vec<u128> = (0..10).map(|_| generator.next_id()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "bit-field layout must sum to 63 bits or fewer")]
+    fn builder_rejects_a_layout_wider_than_63_bits() {
+        SnowflakeGenerator::builder()
+            .timestamp_bits(44)
+            .sequence_bits(12)
+            .worker_id_bits(5)
+            .data_center_id_bits(5)
+            .build();
+    }
+
+    #[test]
+    #[should_panic(expected = "worker id is out of range")]
+    fn builder_rejects_a_worker_id_outside_the_configured_bits() {
+        SnowflakeGenerator::builder()
+            .worker_id_bits(1)
+            .worker_id(2)
+            .build();
+    }
+
+    #[test]
+    fn decode_recovers_the_fields_used_to_pack_the_id() {
+        let mut generator = SnowflakeGenerator::new(3, 7, 0);
+        let id = generator.next_id();
+
+        let parts = generator.decode(id);
+
+        assert_eq!(parts.worker_id, 3);
+        assert_eq!(parts.data_center_id, 7);
+        assert_eq!(
+            parts.datetime,
+            UNIX_EPOCH + Duration::from_millis(parts.timestamp_millis as u64)
+        );
+    }
+
+    #[test]
+    fn decode_stays_consistent_with_a_custom_bit_field_layout() {
+        let mut generator = SnowflakeGenerator::builder()
+            .timestamp_bits(44)
+            .sequence_bits(17)
+            .worker_id_bits(1)
+            .data_center_id_bits(1)
+            .worker_id(1)
+            .data_center_id(0)
+            .build();
+
+        let id = generator.next_id();
+        let parts = generator.decode(id);
+
+        assert_eq!(parts.worker_id, 1);
+        assert_eq!(parts.data_center_id, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "clock moved backwards")]
+    fn next_id_panics_on_clock_backward_by_default() {
+        let mut generator = SnowflakeGenerator::new(0, 0, 0);
+        generator.last_time_millis += 1_000;
+
+        generator.next_id();
+    }
+
+    #[test]
+    fn next_id_waits_out_a_clock_backward_within_tolerance() {
+        let mut generator = SnowflakeGenerator::builder()
+            .on_clock_backward(ClockBackwardPolicy::WaitUpTo(Duration::from_millis(50)))
+            .build();
+        generator.last_time_millis += 5;
+
+        let before = generator.last_time_millis;
+        let id = generator.next_id();
+
+        assert!(generator.decode(id).timestamp_millis >= before);
+    }
+
+    #[test]
+    fn try_build_reports_an_out_of_range_worker_id_instead_of_panicking() {
+        let result = SnowflakeGenerator::builder()
+            .worker_id_bits(1)
+            .worker_id(2)
+            .try_build();
+
+        assert!(matches!(result, Err(SnowflakeError::WorkerIdOutOfRange)));
+    }
+
+    #[test]
+    fn try_next_id_reports_a_clock_backward_instead_of_panicking() {
+        let mut generator = SnowflakeGenerator::new(0, 0, 0);
+        generator.last_time_millis += 1_000;
+
+        let err = generator.try_next_id().unwrap_err();
+
+        assert!(matches!(err, SnowflakeError::ClockMovedBackwards { .. }));
+    }
+
+    #[test]
+    fn next_id_batch_produces_n_strictly_monotonic_ids() {
+        let mut generator = SnowflakeGenerator::new(0, 0, 0);
+
+        let ids = generator.next_id_batch(1_000);
+
+        assert_eq!(ids.len(), 1_000);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn next_id_batch_continues_after_a_prior_id_in_the_same_millisecond() {
+        let mut generator = SnowflakeGenerator::new(0, 0, 0);
+
+        let first = generator.next_id();
+        let batch = generator.next_id_batch(10);
+
+        assert!(batch.iter().all(|&id| id > first));
+        assert!(batch.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn next_id_batch_spans_a_millisecond_boundary() {
+        let mut generator = SnowflakeGenerator::builder()
+            .sequence_bits(2)
+            .worker_id_bits(5)
+            .data_center_id_bits(5)
+            .build();
+
+        let ids = generator.next_id_batch(10);
+
+        assert_eq!(ids.len(), 10);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+        let decoded_timestamps: Vec<u128> = ids
+            .iter()
+            .map(|id| generator.decode(*id).timestamp_millis)
+            .collect();
+        assert!(decoded_timestamps.iter().max() > decoded_timestamps.iter().min());
+    }
+
+    #[test]
+    #[should_panic(expected = "clock moved backwards")]
+    fn next_id_panics_when_clock_backward_exceeds_tolerance() {
+        let mut generator = SnowflakeGenerator::builder()
+            .on_clock_backward(ClockBackwardPolicy::WaitUpTo(Duration::from_millis(5)))
+            .build();
+        generator.last_time_millis += 1_000;
+
+        generator.next_id();
+    }
+
+    #[test]
+    #[should_panic(expected = "clock moved backwards")]
+    fn try_next_id_panics_when_policy_is_panic() {
+        let mut generator = SnowflakeGenerator::builder()
+            .on_clock_backward(ClockBackwardPolicy::Panic)
+            .build();
+        generator.last_time_millis += 1_000;
+
+        // Unlike the default `Error` policy, `Panic` panics even through the fallible API.
+        let _ = generator.try_next_id();
+    }
+
+    #[test]
+    fn concurrent_generator_produces_unique_ids_across_threads() {
+        let generator = ConcurrentSnowflakeGenerator::new(SnowflakeGenerator::new(0, 0, 0));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let generator = generator.clone();
+                thread::spawn(move || (0..100).map(|_| generator.next_id()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut ids: Vec<u128> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("thread panicked"))
+            .collect();
+
+        let total = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+
+        assert_eq!(ids.len(), total);
+    }
+
+    #[test]
+    fn concurrent_generator_stays_unique_across_sequence_exhaustion() {
+        let generator = ConcurrentSnowflakeGenerator::new(
+            SnowflakeGenerator::builder()
+                .sequence_bits(2)
+                .worker_id_bits(5)
+                .data_center_id_bits(5)
+                .build(),
+        );
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let generator = generator.clone();
+                thread::spawn(move || (0..5_000).map(|_| generator.next_id()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut ids: Vec<u128> = handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("thread panicked"))
+            .collect();
+
+        let total = ids.len();
+        ids.sort_unstable();
+        ids.dedup();
+
+        assert_eq!(ids.len(), total);
+    }
 }